@@ -0,0 +1,128 @@
+//! Abstracts over where the combinations database comes from: a local file, or fetched fresh
+//! from the game's own servers. Mirrors the sync/async client split used by projects like the
+//! Solana SDK, so the plain CLI can call the blocking `load` while anything already running
+//! inside an async runtime can await `load_async` instead.
+use std::{fs, path::PathBuf};
+
+use async_trait::async_trait;
+use clap::ValueEnum;
+
+use crate::structures::{binary::BinaryFormatError, game_status::ElementsList};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+/// How the database and history files are encoded on disk.
+pub enum FileFormat {
+    /// Human-readable JSON (the default).
+    Json,
+    /// The compact binary format from `structures::binary`.
+    Binary,
+}
+
+impl std::fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Json => "json",
+            Self::Binary => "binary",
+        })
+    }
+}
+
+#[derive(Debug)]
+/// An error while loading an `ElementsList` from a `DataSource`.
+pub enum DataSourceError {
+    Io(std::io::Error),
+    Http(reqwest::Error),
+    Binary(BinaryFormatError),
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for DataSourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => f.write_fmt(format_args!("I/O error: {err}")),
+            Self::Http(err) => f.write_fmt(format_args!("HTTP error: {err}")),
+            Self::Binary(err) => f.write_fmt(format_args!("binary decode error: {err}")),
+            Self::Json(err) => f.write_fmt(format_args!("JSON decode error: {err}")),
+        }
+    }
+}
+impl std::error::Error for DataSourceError {}
+
+impl From<std::io::Error> for DataSourceError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+impl From<reqwest::Error> for DataSourceError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Http(err)
+    }
+}
+impl From<BinaryFormatError> for DataSourceError {
+    fn from(err: BinaryFormatError) -> Self {
+        Self::Binary(err)
+    }
+}
+impl From<serde_json::Error> for DataSourceError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// Decodes a downloaded or locally-read buffer into an `ElementsList`. This is the
+/// normalization step: whether the bytes came from disk or over HTTPS, they're parsed through
+/// `ElementsList`'s own `Deserialize`/`from_bytes`, so a remote export lands in the exact same
+/// `AlchemyElement` model (down to the string-number serde modules) as a local file would.
+fn parse(bytes: &[u8], format: FileFormat) -> Result<ElementsList, DataSourceError> {
+    match format {
+        FileFormat::Json => Ok(serde_json::from_slice(bytes)?),
+        FileFormat::Binary => Ok(ElementsList::from_bytes(bytes)?),
+    }
+}
+
+#[async_trait]
+/// Somewhere an `ElementsList` can be loaded from.
+pub trait DataSource {
+    /// Loads the database, blocking the current thread.
+    fn load(&self) -> Result<ElementsList, DataSourceError>;
+
+    /// Loads the database without blocking the current thread.
+    async fn load_async(&self) -> Result<ElementsList, DataSourceError>;
+}
+
+/// Reads the database from a local file, in the given `FileFormat`. This is the behavior the
+/// program always had, wrapped behind `DataSource` so it's interchangeable with `HttpSource`.
+pub struct FileSource {
+    pub path: PathBuf,
+    pub format: FileFormat,
+}
+
+#[async_trait]
+impl DataSource for FileSource {
+    fn load(&self) -> Result<ElementsList, DataSourceError> {
+        parse(&fs::read(&self.path)?, self.format)
+    }
+
+    async fn load_async(&self) -> Result<ElementsList, DataSourceError> {
+        parse(&tokio::fs::read(&self.path).await?, self.format)
+    }
+}
+
+/// Downloads the database over HTTPS, for the `Update` subcommand.
+pub struct HttpSource {
+    pub url: String,
+    pub format: FileFormat,
+}
+
+#[async_trait]
+impl DataSource for HttpSource {
+    fn load(&self) -> Result<ElementsList, DataSourceError> {
+        let bytes = reqwest::blocking::get(&self.url)?.error_for_status()?.bytes()?;
+        parse(&bytes, self.format)
+    }
+
+    async fn load_async(&self) -> Result<ElementsList, DataSourceError> {
+        let bytes = reqwest::get(&self.url).await?.error_for_status()?.bytes().await?;
+        parse(&bytes, self.format)
+    }
+}
@@ -1,9 +1,11 @@
 //! A program that displays combinations for Little Alchemy 2.
 use std::{fs::File, io::BufReader, path::{Path, PathBuf}};
 
+use chrono::Local;
 use clap::{CommandFactory, error::ErrorKind, Parser, Subcommand, ValueHint::FilePath};
 use serde::de::DeserializeOwned;
-use structures::{game_status::GameStatus, display_combinations_list, history::History, AlchemyElement, AlchemyElementError};
+use data_source::{DataSource, FileFormat, FileSource, HttpSource};
+use structures::{game_status::GameStatus, display_combinations_list, format_elements_list, history::{History, HistoryItem}, output::OutputFormat, query, AlchemyElement, AlchemyElementError, Combination};
 
 #[derive(Debug, Subcommand)]
 /// The subcommands for the program.
@@ -28,18 +30,53 @@ pub enum Command {
     },
     /// Display how to finish the game
     Finish {
-        /// Display JavaScript commands instead of human-readable instructions
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
+        /// Compute the minimal set of combinations instead of the first plan found
         #[arg(long)]
-        javascript: bool,
+        minimal: bool,
     },
     /// Display how to get an element
     Get {
         /// Element to display
         element: String,
 
-        /// Display JavaScript commands instead of human-readable instructions
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
+        /// Count a combination shared by several ingredients once when choosing the plan,
+        /// instead of once per ingredient that needs it
+        #[arg(long)]
+        minimal: bool,
+    },
+    /// Combine two elements and record the result in the history
+    Combine {
+        /// First element to combine
+        first: String,
+
+        /// Second element to combine
+        second: String,
+    },
+    /// Enter an interactive crafting session
+    Repl,
+    /// Check the database for inconsistencies
+    Lint {
+        /// Apply the auto-fixable diagnostics and rewrite the database file
         #[arg(long)]
-        javascript: bool,
+        fix: bool,
+    },
+    /// Fetch the latest combinations database from a URL and overwrite `--file` with it
+    Update {
+        /// URL to download the database from
+        url: String,
+    },
+    /// Select elements with a query, e.g. `[prime]/products/[final]`
+    Query {
+        /// Query string to evaluate
+        query: String,
     },
 }
 
@@ -54,6 +91,14 @@ struct Cli {
     #[arg(long, default_value="history.json", value_hint=FilePath)]
     history_file: PathBuf,
 
+    /// Profile file seeding additional acquired elements (see the profile format docs)
+    #[arg(long, value_hint=FilePath)]
+    profile: Option<PathBuf>,
+
+    /// Encoding of `--file` and `--history-file`
+    #[arg(long, value_enum, default_value_t = FileFormat::Json)]
+    data_format: FileFormat,
+
     #[arg(long)]
     no_history: bool,
 
@@ -79,8 +124,14 @@ impl Cli {
 
     fn parse() -> Self {
         let args: Self = Parser::parse();
-        Cli::check_file_exists(&args.file);
-        Cli::check_file_exists(&args.history_file);
+        // `Update` writes `--file` rather than reading it, so it's fine if it doesn't exist yet.
+        if !matches!(args.command, Command::Update { .. }) {
+            Cli::check_file_exists(&args.file);
+            Cli::check_file_exists(&args.history_file);
+            if let Some(profile) = &args.profile {
+                Cli::check_file_exists(profile);
+            }
+        }
         args
     }
 }
@@ -92,17 +143,54 @@ fn read_json<T: DeserializeOwned>(file: &Path) -> Result<T, Box<dyn std::error::
     x.map_err(std::convert::Into::into)
 }
 
+fn read_elements(file: &Path, format: FileFormat) -> Result<structures::game_status::ElementsList, Box<dyn std::error::Error>> {
+    FileSource { path: file.to_path_buf(), format }.load().map_err(Into::into)
+}
+
+fn read_history(file: &Path, format: FileFormat) -> Result<History, Box<dyn std::error::Error>> {
+    match format {
+        FileFormat::Json => read_json(file),
+        FileFormat::Binary => Ok(History::from_bytes(&std::fs::read(file)?)?),
+    }
+}
+
+fn write_history(file: &Path, format: FileFormat, history: &History) -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = match format {
+        FileFormat::Json => serde_json::to_string_pretty(history)?.into_bytes(),
+        FileFormat::Binary => history.to_bytes(),
+    };
+    std::fs::write(file, bytes)?;
+    Ok(())
+}
+
+mod data_source;
+mod profile;
+mod repl;
 mod structures;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Cli::parse();
 
+    if let Command::Update { url } = &args.command {
+        // Fetching is the one place this otherwise-synchronous CLI has an async runtime handy
+        // to await, so it's what exercises `DataSource::load_async` rather than `load`.
+        let elements = tokio::runtime::Runtime::new()?
+            .block_on(HttpSource { url: url.clone(), format: args.data_format }.load_async())?;
+        let bytes = match args.data_format {
+            FileFormat::Json => serde_json::to_string_pretty(&elements)?.into_bytes(),
+            FileFormat::Binary => elements.to_bytes(),
+        };
+        std::fs::write(&args.file, bytes)?;
+        println!("Updated {} from {url}", args.file.display());
+        return Ok(());
+    }
+
     let mut data = GameStatus {
-        elements: read_json(&args.file)?,
+        elements: read_elements(&args.file, args.data_format)?,
         history: if args.no_history {
             History::new()
         } else {
-            read_json(&args.history_file)?
+            read_history(&args.history_file, args.data_format)?
         },
         ..Default::default()
     };
@@ -111,6 +199,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         data.check();
     }
 
+    if let Some(profile_path) = &args.profile {
+        for id in profile::load_profile(profile_path, &data)? {
+            if !data.acquired_elements.contains(&id) {
+                data.acquired_elements.push(id);
+            }
+        }
+    }
+
     if let Command::Display { element, .. } = &args.command {
         let element_or_err = AlchemyElement::from_str(element.as_str(), &data);
         match element_or_err {
@@ -127,31 +223,82 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
-    if let Command::Get { element, javascript } = &args.command {
+    if let Command::Get { element, format, minimal } = &args.command {
         let element = AlchemyElement::from_str(element.as_str(), &data)?;
         let name = element.name.clone();
-        let combinations = data.obtain(element.id);
-        if *javascript {
-            display_combinations_list(&combinations[..], &data, Some(element), true);
+        let combinations = if *minimal { data.obtain_optimal(element.id) } else { data.obtain(element.id) };
+        if !matches!(format, OutputFormat::Text) {
+            display_combinations_list(&combinations[..], &data, Some(element), *format);
         } else if combinations.is_empty() {
             assert!(data.acquired_elements.contains(&element.id));
             println!("You already have the {name} in your inventory");
         } else {
             println!("To get the {name}, you must combine:");
-            display_combinations_list(&combinations[..], &data, Some(element), false);
+            display_combinations_list(&combinations[..], &data, Some(element), *format);
+        }
+        return Ok(());
+    }
+
+    if let Command::Combine { first, second } = &args.command {
+        let first = AlchemyElement::from_str(first, &data)?.id;
+        let second = AlchemyElement::from_str(second, &data)?.id;
+        let combination = Combination(first, second);
+        if !data.can_do_combination(&combination) {
+            return Err(format!(
+                "you don't have both {} and {} yet",
+                data.elements[first].name,
+                data.elements[second].name,
+            ).into());
+        }
+        data.combine(&combination);
+        println!("{} gives: {}", combination.display(&data), format_elements_list(&data.elements.get_from_combination(&combination)));
+        if !args.no_history {
+            data.history.0.push(HistoryItem { combination, datetime: Local::now().naive_local() });
+            write_history(&args.history_file, args.data_format, &data.history)?;
+        }
+        return Ok(());
+    }
+
+    if let Command::Repl = &args.command {
+        repl::run(&mut data, &args.history_file, args.no_history);
+        return Ok(());
+    }
+
+    if let Command::Lint { fix } = &args.command {
+        let diagnostics = data.lint();
+        for diag in &diagnostics {
+            println!("[{:?}] {} (element #{}): {}", diag.severity, diag.code, diag.element_id, diag.message);
+        }
+        if diagnostics.is_empty() {
+            println!("No problems found.");
+        } else if *fix {
+            data.apply_fixes(&diagnostics);
+            let bytes = match args.data_format {
+                FileFormat::Json => serde_json::to_string_pretty(&data.elements)?.into_bytes(),
+                FileFormat::Binary => data.elements.to_bytes(),
+            };
+            std::fs::write(&args.file, bytes)?;
+            println!("Applied fixes and wrote {}", args.file.display());
         }
         return Ok(());
     }
 
-    if let Command::Finish { javascript } = &args.command {
-        let combinations = data.finish_game();
-        if *javascript {
-            display_combinations_list(&combinations[..], &data, None, true);
+    if let Command::Query { query } = &args.command {
+        let query = query::parse_query(query)?;
+        let elements = query.eval(&data);
+        println!("{}", format_elements_list(&elements));
+        return Ok(());
+    }
+
+    if let Command::Finish { format, minimal } = &args.command {
+        let combinations = if *minimal { data.finish_game_optimal() } else { data.finish_game() };
+        if !matches!(format, OutputFormat::Text) {
+            display_combinations_list(&combinations[..], &data, None, *format);
         } else if combinations.is_empty() {
             println!("You already finished the game");
         } else {
             println!("To finish the game, you must combine:");
-            display_combinations_list(&combinations[..], &data, None, false);
+            display_combinations_list(&combinations[..], &data, None, *format);
         }
         return Ok(());
     }
@@ -0,0 +1,78 @@
+//! Composable save-profile config files.
+//!
+//! A profile file lists element names or ids to mark as acquired, one per line, plus two
+//! directives: `%include <path>` pulls in another profile file (resolved relative to the
+//! including file, with cycles rejected) so a "base game" profile can be shared and extended,
+//! and `%unset <element>` removes a previously-included element from the accumulated set.
+use std::{collections::HashSet, fs, path::{Path, PathBuf}};
+
+use crate::structures::{game_status::GameStatus, AlchemyElement};
+
+#[derive(Debug)]
+/// An error while loading a profile file.
+pub enum ProfileError {
+    /// The file (or one of its includes) couldn't be read.
+    Io(PathBuf, std::io::Error),
+    /// A line didn't name a known element.
+    UnknownElement(String),
+    /// A `%include` chain referenced a file it was already loading.
+    CyclicInclude(PathBuf),
+}
+
+impl std::fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(path, err) => f.write_fmt(format_args!("error reading profile {}: {err}", path.display())),
+            Self::UnknownElement(s) => f.write_fmt(format_args!("unknown element in profile: {s}")),
+            Self::CyclicInclude(path) => f.write_fmt(format_args!("cyclic %include of {}", path.display())),
+        }
+    }
+}
+impl std::error::Error for ProfileError {}
+
+/// Loads `path` (and anything it `%include`s) into a flattened set of acquired element ids.
+pub fn load_profile(path: &Path, data: &GameStatus) -> Result<HashSet<u16>, ProfileError> {
+    let mut elements = HashSet::new();
+    let mut visiting = vec![];
+    apply_profile(path, data, &mut elements, &mut visiting)?;
+    Ok(elements)
+}
+
+fn apply_profile(
+    path: &Path,
+    data: &GameStatus,
+    elements: &mut HashSet<u16>,
+    visiting: &mut Vec<PathBuf>,
+) -> Result<(), ProfileError> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if visiting.contains(&canonical) {
+        return Err(ProfileError::CyclicInclude(canonical));
+    }
+    visiting.push(canonical);
+
+    let content = fs::read_to_string(path).map_err(|err| ProfileError::Io(path.to_path_buf(), err))?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(include_path) = line.strip_prefix("%include ") {
+            apply_profile(&base_dir.join(include_path.trim()), data, elements, visiting)?;
+        } else if let Some(name) = line.strip_prefix("%unset ") {
+            elements.remove(&resolve(name.trim(), data)?);
+        } else {
+            elements.insert(resolve(line, data)?);
+        }
+    }
+
+    visiting.pop();
+    Ok(())
+}
+
+fn resolve(name: &str, data: &GameStatus) -> Result<u16, ProfileError> {
+    AlchemyElement::from_str(name, data)
+        .map(|element| element.id)
+        .map_err(|_| ProfileError::UnknownElement(name.to_string()))
+}
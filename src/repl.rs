@@ -0,0 +1,164 @@
+//! An interactive read-eval loop that lets the user play through combinations one at a time,
+//! keeping the acquired elements and history alive across inputs.
+use std::{
+    fs::File,
+    io::{self, BufReader, Write},
+    path::Path,
+};
+
+use chrono::Local;
+
+use crate::structures::{
+    format_elements_list, game_status::GameStatus, history::HistoryItem, AlchemyElement, Combination,
+};
+
+fn parse_elements<'a>(line: &str, data: &'a GameStatus) -> Result<Vec<&'a AlchemyElement>, String> {
+    line
+        .split([',', '+'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| AlchemyElement::from_str(s, data).map_err(|err| err.to_string()))
+        .collect()
+}
+
+fn show_combinations(element: &AlchemyElement, data: &GameStatus) {
+    println!("Element #{}: {}", element.id, element.name);
+    let available: Vec<&Combination> = element.combinations.iter()
+        .filter(|comb| data.can_do_combination(comb) && !data.history.has_combination(comb))
+        .collect();
+    if available.is_empty() {
+        println!("No combinations currently available for this element.");
+    } else {
+        for comb in available {
+            println!("= {}", comb.display(data));
+        }
+    }
+}
+
+fn combine(data: &mut GameStatus, first: u16, second: u16, no_history: bool) {
+    let combination = Combination(first, second);
+    if !data.can_do_combination(&combination) {
+        println!(
+            "You don't have both {} and {} yet.",
+            data.elements[first].name,
+            data.elements[second].name,
+        );
+        return;
+    }
+    let results = format_elements_list(&data.elements.get_from_combination(&combination));
+    if results.is_empty() {
+        println!("{} doesn't combine into anything.", combination.display(data));
+        return;
+    }
+    data.combine(&combination);
+    if !no_history {
+        data.history.0.push(HistoryItem { combination: combination.clone(), datetime: Local::now().naive_local() });
+    }
+    println!("{} gives: {results}", combination.display(data));
+}
+
+fn save_history(data: &GameStatus, history_file: &Path) {
+    match serde_json::to_string(&data.history) {
+        Ok(json) => match std::fs::write(history_file, json) {
+            Ok(()) => println!("Saved history to {}", history_file.display()),
+            Err(err) => println!("error saving history: {err}"),
+        },
+        Err(err) => println!("error serializing history: {err}"),
+    }
+}
+
+fn load_history(data: &mut GameStatus, history_file: &Path) {
+    match File::open(history_file) {
+        Ok(file) => match serde_json::from_reader(BufReader::new(file)) {
+            Ok(history) => {
+                data.history = history;
+                println!("Loaded history from {}", history_file.display());
+            },
+            Err(err) => println!("error parsing {}: {err}", history_file.display()),
+        },
+        Err(err) => println!("error opening {}: {err}", history_file.display()),
+    }
+}
+
+fn reset(data: &mut GameStatus) {
+    data.acquired_elements.clear();
+    data.check();
+    println!("Acquired elements reset to the starting state.");
+}
+
+fn show_have(data: &GameStatus) {
+    let elements: Vec<&AlchemyElement> = data.acquired_elements.iter()
+        .filter_map(|id| data.elements.get(*id))
+        .collect();
+    println!("You have: {}", format_elements_list(&elements));
+}
+
+fn handle_meta_command(command: &str, data: &mut GameStatus, history_file: &Path) {
+    match command.trim() {
+        "save" => save_history(data, history_file),
+        "load" => load_history(data, history_file),
+        "reset" => reset(data),
+        "have" => show_have(data),
+        other => println!("unknown command: :{other} (try :save, :load, :reset or :have)"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structures::{game_status::GameStatus, history::History};
+
+    #[test]
+    fn save_then_load_round_trips_history() {
+        let history_file = std::env::temp_dir().join("littlealchemy2-cheat-repl-test-save-load-history.json");
+
+        let mut data = GameStatus::default();
+        data.history.0.push(HistoryItem { combination: Combination(1, 2), datetime: Local::now().naive_local() });
+        save_history(&data, &history_file);
+
+        let mut loaded = GameStatus { history: History::new(), ..GameStatus::default() };
+        load_history(&mut loaded, &history_file);
+
+        std::fs::remove_file(&history_file).ok();
+
+        assert_eq!(loaded.history.0.len(), data.history.0.len());
+        assert_eq!(loaded.history.0[0].combination, data.history.0[0].combination);
+    }
+}
+
+/// Runs the interactive crafting REPL until the user closes stdin.
+pub fn run(data: &mut GameStatus, history_file: &Path, no_history: bool) {
+    println!("Entering interactive mode. Type an element name/id, two separated by ',' or '+', or :help.");
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        io::stdout().flush().ok();
+
+        let mut line = String::new();
+        if stdin.read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(command) = line.strip_prefix(':') {
+            if command.trim() == "help" {
+                println!("Commands: :save, :load, :reset, :have. Otherwise type an element, or two separated by ',' or '+'.");
+            } else {
+                handle_meta_command(command, data, history_file);
+            }
+            continue;
+        }
+
+        match parse_elements(line, data) {
+            Ok(elements) => match elements.as_slice() {
+                [element] => show_combinations(element, data),
+                [first, second] => combine(data, first.id, second.id, no_history),
+                _ => println!("error: expected one or two elements"),
+            },
+            Err(err) => println!("error: {err}"),
+        }
+    }
+}
@@ -0,0 +1,398 @@
+//! A compact, self-describing binary encoding for an `ElementsList` and a `History`, used as
+//! a smaller and faster alternative to their JSON representations.
+use super::{condition::Condition, game_status::ElementsList, history::{History, HistoryItem}, AlchemyElement, Combination};
+
+/// Magic bytes identifying the format, followed by a version byte so future schema
+/// changes can stay backward-compatible.
+const MAGIC: &[u8; 4] = b"LA2C";
+const VERSION: u8 = 1;
+
+/// Magic bytes identifying the history format, with its own version byte.
+const HISTORY_MAGIC: &[u8; 4] = b"LA2H";
+const HISTORY_VERSION: u8 = 1;
+
+const FLAG_PRIME: u8 = 1 << 0;
+const FLAG_BASE: u8 = 1 << 1;
+const FLAG_HIDDEN: u8 = 1 << 2;
+const FLAG_FINAL: u8 = 1 << 3;
+
+#[derive(Debug)]
+/// An error while decoding an `ElementsList` or a `History` from its binary format.
+pub enum BinaryFormatError {
+    /// The buffer ended before the expected data was read.
+    UnexpectedEnd,
+    /// The magic bytes don't match `LA2C` (or `LA2H` for a history).
+    BadMagic,
+    /// The format version isn't supported by this build.
+    UnsupportedVersion(u8),
+    /// A string wasn't valid UTF-8.
+    InvalidUtf8,
+    /// A condition tag didn't match any known variant.
+    UnknownConditionTag(u8),
+    /// A history entry's timestamp couldn't be converted back to a `NaiveDateTime`.
+    InvalidTimestamp(i64),
+}
+
+impl std::fmt::Display for BinaryFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedEnd => f.write_str("unexpected end of buffer"),
+            Self::BadMagic => f.write_str("bad magic bytes (not a littlealchemy2-cheat binary database)"),
+            Self::UnsupportedVersion(v) => f.write_fmt(format_args!("unsupported binary format version: {v}")),
+            Self::InvalidUtf8 => f.write_str("invalid UTF-8 in element name"),
+            Self::UnknownConditionTag(tag) => f.write_fmt(format_args!("unknown condition tag: {tag}")),
+            Self::InvalidTimestamp(ts) => f.write_fmt(format_args!("invalid history timestamp: {ts}")),
+        }
+    }
+}
+impl std::error::Error for BinaryFormatError {}
+
+struct Writer(Vec<u8>);
+impl Writer {
+    fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn i64(&mut self, value: i64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn str(&mut self, value: &str) {
+        self.u16(u16::try_from(value.len()).unwrap_or(u16::MAX));
+        self.0.extend_from_slice(value.as_bytes());
+    }
+
+    fn ids(&mut self, ids: &[u16]) {
+        self.u16(u16::try_from(ids.len()).unwrap_or(u16::MAX));
+        for id in ids {
+            self.u16(*id);
+        }
+    }
+
+    fn condition(&mut self, condition: &Condition) {
+        match condition {
+            Condition::None => self.u8(0),
+            Condition::Progress(total) => {
+                self.u8(1);
+                self.u32(u32::try_from(*total).unwrap_or(u32::MAX));
+            },
+            Condition::Elements(elements, min) => {
+                self.u8(2);
+                self.ids(elements);
+                self.u32(u32::try_from(*min).unwrap_or(u32::MAX));
+            },
+            Condition::All(conditions) => {
+                self.u8(3);
+                self.u16(u16::try_from(conditions.len()).unwrap_or(u16::MAX));
+                for condition in conditions {
+                    self.condition(condition);
+                }
+            },
+            Condition::Any(conditions) => {
+                self.u8(4);
+                self.u16(u16::try_from(conditions.len()).unwrap_or(u16::MAX));
+                for condition in conditions {
+                    self.condition(condition);
+                }
+            },
+        }
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], BinaryFormatError> {
+        let end = self.pos.checked_add(len).ok_or(BinaryFormatError::UnexpectedEnd)?;
+        let slice = self.data.get(self.pos..end).ok_or(BinaryFormatError::UnexpectedEnd)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, BinaryFormatError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, BinaryFormatError> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, BinaryFormatError> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn i64(&mut self) -> Result<i64, BinaryFormatError> {
+        Ok(i64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    fn str(&mut self) -> Result<String, BinaryFormatError> {
+        let len = self.u16()? as usize;
+        String::from_utf8(self.bytes(len)?.to_vec()).map_err(| _ | BinaryFormatError::InvalidUtf8)
+    }
+
+    fn ids(&mut self) -> Result<Vec<u16>, BinaryFormatError> {
+        let len = self.u16()?;
+        (0..len).map(| _ | self.u16()).collect()
+    }
+
+    fn condition(&mut self) -> Result<Condition, BinaryFormatError> {
+        match self.u8()? {
+            0 => Ok(Condition::None),
+            1 => Ok(Condition::Progress(self.u32()? as usize)),
+            2 => {
+                let elements = self.ids()?;
+                let min = self.u32()? as usize;
+                Ok(Condition::Elements(elements, min))
+            },
+            3 => {
+                let count = self.u16()?;
+                Ok(Condition::All((0..count).map(| _ | self.condition()).collect::<Result<_, _>>()?))
+            },
+            4 => {
+                let count = self.u16()?;
+                Ok(Condition::Any((0..count).map(| _ | self.condition()).collect::<Result<_, _>>()?))
+            },
+            tag => Err(BinaryFormatError::UnknownConditionTag(tag)),
+        }
+    }
+}
+
+impl ElementsList {
+    /// Encodes this `ElementsList` into the compact binary format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Writer(vec![]);
+        writer.0.extend_from_slice(MAGIC);
+        writer.u8(VERSION);
+        writer.u32(u32::try_from(self.len()).unwrap_or(u32::MAX));
+
+        for element in self.iter() {
+            writer.u16(element.id);
+            writer.str(&element.name);
+
+            let mut flags = 0;
+            if element.prime { flags |= FLAG_PRIME; }
+            if element.base { flags |= FLAG_BASE; }
+            if element.hidden { flags |= FLAG_HIDDEN; }
+            if element.final_ { flags |= FLAG_FINAL; }
+            writer.u8(flags);
+
+            writer.u16(u16::try_from(element.combinations.len()).unwrap_or(u16::MAX));
+            for comb in &element.combinations {
+                writer.u16(comb.0);
+                writer.u16(comb.1);
+            }
+
+            writer.condition(&element.condition);
+            writer.ids(&element.can_create);
+        }
+
+        writer.0
+    }
+
+    /// Decodes an `ElementsList` previously produced by `to_bytes`.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, BinaryFormatError> {
+        let mut reader = Reader::new(data);
+        if reader.bytes(MAGIC.len())? != MAGIC {
+            return Err(BinaryFormatError::BadMagic);
+        }
+        let version = reader.u8()?;
+        if version != VERSION {
+            return Err(BinaryFormatError::UnsupportedVersion(version));
+        }
+
+        let count = reader.u32()?;
+        let mut list = Self::new();
+        for _ in 0..count {
+            let id = reader.u16()?;
+            let name = reader.str()?;
+            let flags = reader.u8()?;
+
+            let combinations_count = reader.u16()?;
+            let mut combinations = Vec::with_capacity(combinations_count as usize);
+            for _ in 0..combinations_count {
+                combinations.push(Combination(reader.u16()?, reader.u16()?));
+            }
+
+            let condition = reader.condition()?;
+            let can_create = reader.ids()?;
+
+            list.0.insert(id, AlchemyElement {
+                id,
+                name,
+                combinations,
+                prime: flags & FLAG_PRIME != 0,
+                base: flags & FLAG_BASE != 0,
+                hidden: flags & FLAG_HIDDEN != 0,
+                final_: flags & FLAG_FINAL != 0,
+                condition,
+                can_create,
+            });
+        }
+
+        Ok(list)
+    }
+}
+
+impl History {
+    /// Encodes this `History` into the compact binary format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Writer(vec![]);
+        writer.0.extend_from_slice(HISTORY_MAGIC);
+        writer.u8(HISTORY_VERSION);
+        writer.u32(u32::try_from(self.0.len()).unwrap_or(u32::MAX));
+
+        for item in self.iter() {
+            writer.u16(item.combination.0);
+            writer.u16(item.combination.1);
+            writer.i64(item.datetime.and_utc().timestamp());
+        }
+
+        writer.0
+    }
+
+    /// Decodes a `History` previously produced by `to_bytes`.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, BinaryFormatError> {
+        let mut reader = Reader::new(data);
+        if reader.bytes(HISTORY_MAGIC.len())? != HISTORY_MAGIC {
+            return Err(BinaryFormatError::BadMagic);
+        }
+        let version = reader.u8()?;
+        if version != HISTORY_VERSION {
+            return Err(BinaryFormatError::UnsupportedVersion(version));
+        }
+
+        let count = reader.u32()?;
+        let mut items = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let a = reader.u16()?;
+            let b = reader.u16()?;
+            let timestamp = reader.i64()?;
+            let datetime = chrono::DateTime::from_timestamp_millis(timestamp * 1000)
+                .map(|dt| dt.naive_utc())
+                .ok_or(BinaryFormatError::InvalidTimestamp(timestamp))?;
+            items.push(HistoryItem {
+                combination: Combination(a, b),
+                datetime,
+            });
+        }
+
+        Ok(Self(items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structures::condition::Condition;
+
+    fn sample_elements() -> ElementsList {
+        let mut list = ElementsList::new();
+        list.0.insert(0, AlchemyElement {
+            id: 0,
+            name: "water".to_string(),
+            combinations: vec![],
+            prime: true,
+            base: false,
+            hidden: false,
+            final_: false,
+            condition: Condition::None,
+            can_create: vec![1, 2],
+        });
+        list.0.insert(1, AlchemyElement {
+            id: 1,
+            name: "steam".to_string(),
+            combinations: vec![Combination(0, 2)],
+            prime: false,
+            base: false,
+            hidden: true,
+            final_: false,
+            condition: Condition::Elements(vec![0, 2], 1),
+            can_create: vec![],
+        });
+        list.0.insert(2, AlchemyElement {
+            id: 2,
+            name: "fire".to_string(),
+            combinations: vec![],
+            prime: true,
+            base: false,
+            hidden: false,
+            final_: true,
+            condition: Condition::All(vec![Condition::Progress(3), Condition::Any(vec![Condition::None])]),
+            can_create: vec![1],
+        });
+        list
+    }
+
+    fn assert_elements_eq(a: &AlchemyElement, b: &AlchemyElement) {
+        assert_eq!(a.id, b.id);
+        assert_eq!(a.name, b.name);
+        assert_eq!(a.combinations, b.combinations);
+        assert_eq!(a.prime, b.prime);
+        assert_eq!(a.base, b.base);
+        assert_eq!(a.hidden, b.hidden);
+        assert_eq!(a.final_, b.final_);
+        assert_eq!(a.condition, b.condition);
+        assert_eq!(a.can_create, b.can_create);
+    }
+
+    #[test]
+    fn elements_list_round_trips_through_binary() {
+        let original = sample_elements();
+        let bytes = original.to_bytes();
+        let decoded = ElementsList::from_bytes(&bytes).expect("valid binary database");
+
+        assert_eq!(decoded.len(), original.len());
+        for element in original.iter() {
+            assert_elements_eq(element, decoded.get(element.id).expect("element present after round trip"));
+        }
+    }
+
+    #[test]
+    fn history_round_trips_through_binary() {
+        let original = History(vec![
+            HistoryItem {
+                combination: Combination(0, 1),
+                datetime: chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap().naive_utc(),
+            },
+            HistoryItem {
+                combination: Combination(2, 1),
+                datetime: chrono::DateTime::from_timestamp(1_700_000_100, 0).unwrap().naive_utc(),
+            },
+        ]);
+        let bytes = original.to_bytes();
+        let decoded = History::from_bytes(&bytes).expect("valid binary history");
+
+        assert_eq!(decoded.0.len(), original.0.len());
+        for (a, b) in original.0.iter().zip(decoded.0.iter()) {
+            assert_eq!(a.combination, b.combination);
+            assert_eq!(a.datetime, b.datetime);
+        }
+    }
+
+    #[test]
+    fn binary_database_is_smaller_than_json() {
+        let elements = sample_elements();
+        let json_len = serde_json::to_string(&elements.iter().collect::<Vec<_>>()).unwrap().len();
+        let binary_len = elements.to_bytes().len();
+
+        assert!(
+            binary_len < json_len,
+            "binary encoding ({binary_len} bytes) should be smaller than JSON ({json_len} bytes)",
+        );
+    }
+}
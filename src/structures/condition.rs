@@ -13,6 +13,10 @@ pub enum Condition {
     Progress(usize),
     /// At least n elements from the list must be discovered.
     Elements(Vec<u16>, usize),
+    /// Every sub-condition must be satisfied.
+    All(Vec<Condition>),
+    /// At least one sub-condition must be satisfied.
+    Any(Vec<Condition>),
 }
 
 impl Condition {
@@ -21,20 +25,50 @@ impl Condition {
         *self == Self::None
     }
 
-    /// Returns a formatted version of the condition according to the given `LittleAlchemy2Database`.
+    /// Returns `true` if this condition is satisfied given the currently acquired elements.
+    pub fn is_satisfied(&self, acquired_elements: &[u16]) -> bool {
+        match self {
+            Self::None => true,
+            Self::Progress(total) => acquired_elements.len() > *total,
+            Self::Elements(elements, min) => {
+                acquired_elements.iter().filter(| el | elements.contains(el)).count() >= *min
+            },
+            Self::All(conditions) => conditions.iter().all(| c | c.is_satisfied(acquired_elements)),
+            Self::Any(conditions) => conditions.iter().any(| c | c.is_satisfied(acquired_elements)),
+        }
+    }
+
+    /// Returns a formatted version of the condition according to the given `GameStatus`.
     pub fn display(&self, data: &GameStatus) {
+        self.display_indented(data, 0);
+    }
+
+    fn display_indented(&self, data: &GameStatus, indent: usize) {
+        let prefix = "  ".repeat(indent);
         match self {
             Self::None => {},
             Self::Progress(total) => {
-                println!("Will be unlocked after discovering {total} elements");
+                println!("{prefix}Will be unlocked after discovering {total} elements");
             },
             Self::Elements(elements, min) => {
                 println!(
-                    "Will be unlocked after discovering {} elements from those: {}",
+                    "{prefix}Will be unlocked after discovering {} elements from those: {}",
                     min,
                     format_elements_list(elements.iter().map(| x | &data.elements[*x]).collect::<Vec<&AlchemyElement>>().as_slice()),
                 );
             },
+            Self::All(conditions) => {
+                println!("{prefix}Will be unlocked after ALL of:");
+                for condition in conditions {
+                    condition.display_indented(data, indent + 1);
+                }
+            },
+            Self::Any(conditions) => {
+                println!("{prefix}Will be unlocked after ANY of:");
+                for condition in conditions {
+                    condition.display_indented(data, indent + 1);
+                }
+            },
         }
     }
 }
@@ -45,6 +79,34 @@ impl Default for Condition {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elements_condition_round_trips_through_json() {
+        let original = Condition::Elements(vec![1, 2, 3], 2);
+
+        let json = serde_json::to_string(&original).expect("serializable");
+        let decoded: Condition = serde_json::from_str(&json).expect("a just-serialized Condition should deserialize back");
+
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn nested_conditions_round_trip_through_json() {
+        let original = Condition::All(vec![
+            Condition::Elements(vec![4, 5], 1),
+            Condition::Any(vec![Condition::Progress(10), Condition::None]),
+        ]);
+
+        let json = serde_json::to_string(&original).expect("serializable");
+        let decoded: Condition = serde_json::from_str(&json).expect("a just-serialized Condition should deserialize back");
+
+        assert_eq!(decoded, original);
+    }
+}
+
 struct ConditionVisitor;
 impl<'de> Visitor<'de> for ConditionVisitor {
     type Value = Condition;
@@ -55,11 +117,12 @@ impl<'de> Visitor<'de> for ConditionVisitor {
 
     fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
     where A: serde::de::MapAccess<'de> {
-        let allowed_types: &[&'static str; 3] = &["none", "progress", "elements"];
+        let allowed_types: &[&'static str; 5] = &["none", "progress", "elements", "all", "any"];
 
         let mut total = None;
         let mut elements = None;
         let mut min = 1;
+        let mut conditions = None;
         let mut type_: Option<String> = None;
 
         while let Some(key) = map.next_key::<String>()? {
@@ -89,8 +152,12 @@ impl<'de> Visitor<'de> for ConditionVisitor {
                 "min" => {
                     min = map.next_value()?;
                 }
+                "conditions" => {
+                    // A nested `Condition::None` is serialized as `null`.
+                    conditions = Some(map.next_value::<Vec<Option<Condition>>>()?.into_iter().map(Option::unwrap_or_default).collect());
+                }
                 _ => {
-                    return Err(serde::de::Error::unknown_field(key.as_str(), &["type", "elements", "min", "total"]));
+                    return Err(serde::de::Error::unknown_field(key.as_str(), &["type", "elements", "min", "total", "conditions"]));
                 }
             }
         }
@@ -111,6 +178,20 @@ impl<'de> Visitor<'de> for ConditionVisitor {
                         Err(serde::de::Error::missing_field("elements"))
                     }
                 },
+                "all" => {
+                    if let Some(real_conditions) = conditions {
+                        Ok(Condition::All(real_conditions))
+                    } else {
+                        Err(serde::de::Error::missing_field("conditions"))
+                    }
+                },
+                "any" => {
+                    if let Some(real_conditions) = conditions {
+                        Ok(Condition::Any(real_conditions))
+                    } else {
+                        Err(serde::de::Error::missing_field("conditions"))
+                    }
+                },
                 _ => {
                     Err(serde::de::Error::unknown_variant(&real_type[..], allowed_types))
                 },
@@ -152,10 +233,25 @@ impl Serialize for Condition {
             Condition::Elements(elements, min) => {
                 let mut map = serializer.serialize_map(None)?;
                 map.serialize_entry("type", "elements")?;
-                map.serialize_entry("elements", elements)?;
+                map.serialize_entry(
+                    "elements",
+                    &elements.iter().map(u16::to_string).collect::<Vec<String>>(),
+                )?;
                 map.serialize_entry("min", min)?;
                 map.end()
             },
+            Condition::All(conditions) => {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("type", "all")?;
+                map.serialize_entry("conditions", conditions)?;
+                map.end()
+            },
+            Condition::Any(conditions) => {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("type", "any")?;
+                map.serialize_entry("conditions", conditions)?;
+                map.end()
+            },
         }
     }
 }
@@ -0,0 +1,169 @@
+use super::{condition::Condition, game_status::GameStatus, Combination};
+
+/// How serious a `Diagnostic` is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// The database is inconsistent and should be repaired before use.
+    Error,
+    /// Something looks suspicious but doesn't break correctness.
+    Warning,
+}
+
+/// An automatic repair that can be applied for a `Diagnostic`.
+#[derive(Clone, Debug)]
+pub enum Fix {
+    /// Recompute `can_create` for the given element from the combination graph.
+    RecomputeCanCreate(u16),
+    /// Remove the given dangling `Combination` from the given element.
+    DropDanglingCombination(u16, Combination),
+}
+
+/// A single problem found while linting a `GameStatus`.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub element_id: u16,
+    pub message: String,
+    pub fix: Option<Fix>,
+}
+
+impl GameStatus {
+    /// Runs every consistency check and returns every problem found, instead of aborting
+    /// on the first one.
+    pub fn lint(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        self.lint_can_create(&mut diagnostics);
+        self.lint_final_elements(&mut diagnostics);
+        self.lint_dangling_combinations(&mut diagnostics);
+        self.lint_condition_elements(&mut diagnostics);
+        self.lint_combination_symmetry(&mut diagnostics);
+        diagnostics
+    }
+
+    fn recomputed_can_create(&self, id: u16) -> Vec<u16> {
+        let mut can_create: Vec<u16> = self.elements.iter()
+            .filter(| item | item.combinations.iter().any(| comb | comb.has(id)))
+            .map(| item | item.id)
+            .collect();
+        can_create.sort_unstable();
+        can_create.dedup();
+        can_create
+    }
+
+    fn lint_can_create(&self, diagnostics: &mut Vec<Diagnostic>) {
+        for item in self.elements.iter() {
+            let expected = self.recomputed_can_create(item.id);
+            if item.can_create != expected {
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    code: "can-create-mismatch",
+                    element_id: item.id,
+                    message: format!(
+                        "can_create mismatch for element #{}: expected {expected:?}, found {:?}",
+                        item.id, item.can_create,
+                    ),
+                    fix: Some(Fix::RecomputeCanCreate(item.id)),
+                });
+            }
+        }
+    }
+
+    fn lint_final_elements(&self, diagnostics: &mut Vec<Diagnostic>) {
+        for item in self.elements.iter() {
+            if item.final_ && !item.can_create.is_empty() {
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    code: "final-can-create",
+                    element_id: item.id,
+                    message: format!("element #{} is final but still has a non-empty can_create", item.id),
+                    fix: Some(Fix::RecomputeCanCreate(item.id)),
+                });
+            }
+        }
+    }
+
+    fn lint_dangling_combinations(&self, diagnostics: &mut Vec<Diagnostic>) {
+        for item in self.elements.iter() {
+            for comb in &item.combinations {
+                for id in [comb.0, comb.1] {
+                    if self.elements.get(id).is_none() {
+                        diagnostics.push(Diagnostic {
+                            severity: Severity::Error,
+                            code: "dangling-combination",
+                            element_id: item.id,
+                            message: format!(
+                                "element #{} has a combination referencing missing element #{id}",
+                                item.id,
+                            ),
+                            fix: Some(Fix::DropDanglingCombination(item.id, comb.clone())),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn lint_condition_elements(&self, diagnostics: &mut Vec<Diagnostic>) {
+        for item in self.elements.iter() {
+            if let Condition::Elements(ids, _) = &item.condition {
+                for &id in ids {
+                    if self.elements.get(id).is_none() {
+                        diagnostics.push(Diagnostic {
+                            severity: Severity::Error,
+                            code: "dangling-condition-element",
+                            element_id: item.id,
+                            message: format!(
+                                "element #{}'s condition references missing element #{id}",
+                                item.id,
+                            ),
+                            fix: None,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn lint_combination_symmetry(&self, diagnostics: &mut Vec<Diagnostic>) {
+        for item in self.elements.iter() {
+            for comb in &item.combinations {
+                for ingredient_id in [comb.0, comb.1] {
+                    let Some(ingredient) = self.elements.get(ingredient_id) else { continue; };
+                    if !ingredient.can_create.contains(&item.id) {
+                        diagnostics.push(Diagnostic {
+                            severity: Severity::Warning,
+                            code: "asymmetric-combination",
+                            element_id: item.id,
+                            message: format!(
+                                "element #{ingredient_id} ({}) is an ingredient of a combination producing #{} ({}) but doesn't list it in can_create",
+                                ingredient.name, item.id, item.name,
+                            ),
+                            fix: Some(Fix::RecomputeCanCreate(ingredient_id)),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies every auto-fixable diagnostic in `diags`, mutating `self.elements`.
+    pub fn apply_fixes(&mut self, diags: &[Diagnostic]) {
+        for diag in diags {
+            match &diag.fix {
+                Some(Fix::RecomputeCanCreate(id)) => {
+                    let can_create = self.recomputed_can_create(*id);
+                    if let Some(element) = self.elements.get_mut(*id) {
+                        element.can_create = can_create;
+                    }
+                },
+                Some(Fix::DropDanglingCombination(id, comb)) => {
+                    if let Some(element) = self.elements.get_mut(*id) {
+                        element.combinations.retain(| c | c != comb);
+                    }
+                },
+                None => {},
+            }
+        }
+    }
+}
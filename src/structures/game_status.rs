@@ -1,14 +1,14 @@
 use std::{collections::{hash_map::{Entry, Values}, HashMap}, ops::{Index, IndexMut}};
 use serde::{de::Visitor, ser::SerializeMap, Deserialize, Serialize};
 
-use super::{condition::Condition, history::History, path::PathToElement, AlchemyElement, Combination};
+use super::{condition::Condition, diagnostics::Severity, history::History, path::PathToElement, AlchemyElement, Combination};
 
 
 #[derive(Debug)]
 /// A list of `AlchemyElement`s.
 ///
-/// This is different from the `LittleAlchemy2Database` struct as it doesn't contain
-/// information like acquired elements.
+/// This is different from `GameStatus` as it doesn't contain information like
+/// acquired elements.
 pub struct ElementsList(pub HashMap<u16, AlchemyElement>);
 impl ElementsList {
     /// Returns an empty `ElementsList`.
@@ -122,11 +122,17 @@ impl GameStatus {
         ret
     }
 
+    /// Seeds `acquired_elements` from the prime/unlocked elements, then runs [`Self::lint`]
+    /// once and reports any error-severity diagnostic instead of aborting on it, so a
+    /// slightly inconsistent database can still be used.
     pub fn check(&mut self) {
         Self::add_prime_elements(&self.elements, &mut self.acquired_elements);
         Self::add_unlocked_elements(&self.elements, &mut self.acquired_elements);
-        self.check_can_create();
-        self.check_final();
+        for diag in self.lint() {
+            if diag.severity == Severity::Error {
+                eprintln!("warning: {} (element #{}): {}", diag.code, diag.element_id, diag.message);
+            }
+        }
     }
 
     fn add_prime_elements(elements: &ElementsList, acquired_elements: &mut Vec<u16>) {
@@ -139,52 +145,11 @@ impl GameStatus {
 
     fn add_unlocked_elements(elements: &ElementsList, acquired_elements: &mut Vec<u16>) {
         for item in elements.iter() {
-            match &item.condition {
-                Condition::None => {},
-                Condition::Progress(total) => {
-                    if acquired_elements.len() > *total {
-                        acquired_elements.push(item.id);
-                    }
-                },
-                Condition::Elements(elements, min) => {
-                    let mut count = 0;
-                    let mut to_add = vec![];
-                    for element in acquired_elements.iter_mut() {
-                        if elements.contains(element) {
-                            count += 1;
-                            if count >= *min {
-                                to_add.push(item.id);
-                                break;
-                            }
-                        }
-                    }
-                    acquired_elements.append(&mut to_add);
-                },
-            }
-        }
-    }
-
-    fn check_can_create(&self) {
-        let mut can_create: HashMap<u16, Vec<u16>> = HashMap::new();
-        for item in self.elements.iter() {
-            for comb in &item.combinations {
-                can_create.entry(comb.0).or_default().push(item.id);
-                can_create.entry(comb.1).or_default().push(item.id);
-            }
-        }
-        for item in self.elements.iter() {
-            if let Some(can_create_ok) = can_create.get_mut(&item.id) {
-                can_create_ok.sort_unstable();
-                can_create_ok.dedup();
-                assert!(item.can_create == *can_create_ok, "can_create mismatch: expected {:?}, found {:?}", can_create[&item.id], item.can_create);
-            }
-        }
-    }
-
-    fn check_final(&self) {
-        for item in self.elements.iter() {
-            if item.is_final() {
-                assert!(item.can_create.is_empty());
+            if !item.condition.is_none()
+                && item.condition.is_satisfied(acquired_elements)
+                && !acquired_elements.contains(&item.id)
+            {
+                acquired_elements.push(item.id);
             }
         }
     }
@@ -212,16 +177,7 @@ impl GameStatus {
     }
 
     pub fn obtain(&self, element_id: u16) -> Vec<Combination> {
-        let path = PathToElement::new(&self.elements[element_id]);
-        let mut element_to_combinations = HashMap::new();
-        let mut recursive = false;
-        loop {
-            match path.advance_one_level(self, &mut element_to_combinations, &[], &mut HashMap::new(), recursive) {
-                Ok(()) => {},
-                Err(x) => {return x;},
-            }
-            recursive = true;
-        }
+        PathToElement::new(&self.elements[element_id]).shortest_path(self)
     }
 
     pub fn finish_game(&self) -> Vec<Combination> {
@@ -268,4 +224,157 @@ impl GameStatus {
 
         combinations
     }
+
+    /// Runs an iterative fixpoint relaxation over a memo of "combination sets": every
+    /// already-acquired or prime element starts resolved with an empty set, and every other
+    /// element is resolved the first time one of its combinations has both ingredients
+    /// resolved, keeping whichever combination yields the smallest union. Unioning
+    /// deduplicates reused sub-recipes, and a set is only replaced by a strictly smaller one,
+    /// so the loop reaches a fixpoint. Each element's choice is made from its ingredients'
+    /// already-chosen sets, so the result is a small set for every resolvable element but not
+    /// always the smallest possible one.
+    ///
+    /// Elements gated by a [`Condition`] are resolved the same way [`path::shortest_path`]
+    /// costs them: no combination of their own is contributed, only the union of whichever
+    /// sub-conditions/listed elements are cheapest to satisfy. See [`condition_set`].
+    fn resolve_optimal_sets(&self) -> HashMap<u16, Vec<Combination>> {
+        let mut sets: HashMap<u16, Vec<Combination>> = HashMap::new();
+        for item in self.elements.iter() {
+            if item.prime || self.acquired_elements.contains(&item.id) {
+                sets.insert(item.id, vec![]);
+            }
+        }
+
+        loop {
+            let mut changed = false;
+            for item in self.elements.iter() {
+                for comb in &item.combinations {
+                    let (Some(a), Some(b)) = (sets.get(&comb.0), sets.get(&comb.1)) else { continue; };
+                    let mut candidate: Vec<Combination> = vec![];
+                    for c in a.iter().chain(b.iter()).chain(std::iter::once(comb)) {
+                        if !candidate.contains(c) {
+                            candidate.push(c.clone());
+                        }
+                    }
+                    let is_smaller = sets.get(&item.id).is_none_or(| current | candidate.len() < current.len());
+                    if is_smaller {
+                        sets.insert(item.id, candidate);
+                        changed = true;
+                    }
+                }
+
+                if let Some(candidate) = condition_set(&item.condition, &sets) {
+                    let is_smaller = sets.get(&item.id).is_none_or(| current | candidate.len() < current.len());
+                    if is_smaller {
+                        sets.insert(item.id, candidate);
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        sets
+    }
+
+    /// Returns a small set of combinations that produces `element_id`, treating the
+    /// recipe graph as an AND/OR DAG: an element needs *both* ingredients of *one* chosen
+    /// combination. See [`Self::resolve_optimal_sets`] for how this is computed.
+    pub fn obtain_optimal(&self, element_id: u16) -> Vec<Combination> {
+        let mut sets = self.resolve_optimal_sets();
+        self.topological_order(sets.remove(&element_id).unwrap_or_default())
+    }
+
+    /// Minimal-combination equivalent of `finish_game`: unions the minimal recipe of every
+    /// not-yet-acquired element, so a combination shared by several elements is only counted
+    /// (and performed) once instead of once per element that needs it.
+    pub fn finish_game_optimal(&self) -> Vec<Combination> {
+        let sets = self.resolve_optimal_sets();
+        let mut combined: Vec<Combination> = vec![];
+        for item in self.elements.iter() {
+            if self.acquired_elements.contains(&item.id) {
+                continue;
+            }
+            for comb in sets.get(&item.id).into_iter().flatten() {
+                if !combined.contains(comb) {
+                    combined.push(comb.clone());
+                }
+            }
+        }
+        self.topological_order(combined)
+    }
+
+    /// Orders `combinations` so that every combination appears after the combinations
+    /// needed to acquire its two ingredients.
+    fn topological_order(&self, mut combinations: Vec<Combination>) -> Vec<Combination> {
+        let mut available: Vec<u16> = self.acquired_elements.clone();
+        for item in self.elements.iter() {
+            if item.prime && !available.contains(&item.id) {
+                available.push(item.id);
+            }
+        }
+
+        let mut ordered = vec![];
+        while !combinations.is_empty() {
+            let (ready, rest): (Vec<_>, Vec<_>) = combinations.into_iter()
+                .partition(| comb | available.contains(&comb.0) && available.contains(&comb.1));
+            if ready.is_empty() {
+                // Shouldn't happen for a well-formed set, but avoid looping forever.
+                ordered.extend(rest);
+                break;
+            }
+            for comb in &ready {
+                for result in self.elements.get_from_combination(comb) {
+                    if !available.contains(&result.id) {
+                        available.push(result.id);
+                    }
+                }
+            }
+            ordered.extend(ready);
+            combinations = rest;
+        }
+        ordered
+    }
+}
+
+/// Returns the combination set a `Condition` would contribute to its element if resolved right
+/// now against `sets`, or `None` if it can't be resolved yet. Mirrors [`path::condition_cost`],
+/// but unions actual combination sets instead of summing costs.
+fn condition_set(condition: &Condition, sets: &HashMap<u16, Vec<Combination>>) -> Option<Vec<Combination>> {
+    match condition {
+        Condition::None => None,
+        Condition::Progress(total) => (sets.len() > *total).then(Vec::new),
+        Condition::Elements(ids, min) => {
+            let mut candidates: Vec<&Vec<Combination>> = ids.iter().filter_map(| id | sets.get(id)).collect();
+            if candidates.len() < *min {
+                return None;
+            }
+            candidates.sort_by_key(| set | set.len());
+            let mut combined: Vec<Combination> = vec![];
+            for set in candidates.into_iter().take(*min) {
+                for comb in set {
+                    if !combined.contains(comb) {
+                        combined.push(comb.clone());
+                    }
+                }
+            }
+            Some(combined)
+        },
+        Condition::All(conditions) => {
+            let mut combined: Vec<Combination> = vec![];
+            for sub in conditions {
+                for comb in condition_set(sub, sets)? {
+                    if !combined.contains(&comb) {
+                        combined.push(comb);
+                    }
+                }
+            }
+            Some(combined)
+        },
+        Condition::Any(conditions) => conditions.iter()
+            .filter_map(| sub | condition_set(sub, sets))
+            .min_by_key(Vec::len),
+    }
 }
@@ -60,9 +60,9 @@ impl Serialize for HistoryItem {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where S: serde::Serializer {
         let mut seq = serializer.serialize_seq(Some(3))?;
-        seq.serialize_element(&self.combination.0)?;
-        seq.serialize_element(&self.combination.1)?;
-        seq.serialize_element(&self.datetime.and_utc().timestamp())?;
+        seq.serialize_element(&self.combination.0.to_string())?;
+        seq.serialize_element(&self.combination.1.to_string())?;
+        seq.serialize_element(&self.datetime.and_utc().timestamp_millis())?;
         seq.end()
     }
 }
@@ -102,3 +102,22 @@ impl Default for History {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_item_round_trips_through_json() {
+        let original = HistoryItem {
+            combination: Combination(12, 345),
+            datetime: chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap().naive_utc(),
+        };
+
+        let json = serde_json::to_string(&original).expect("serializable");
+        let decoded: HistoryItem = serde_json::from_str(&json).expect("a just-serialized HistoryItem should deserialize back");
+
+        assert_eq!(decoded.combination, original.combination);
+        assert_eq!(decoded.datetime, original.datetime);
+    }
+}
@@ -1,7 +1,7 @@
 //! Data structures used for the program.
 use crate::Command;
 
-use database::LittleAlchemy2Database;
+use game_status::GameStatus;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -16,8 +16,8 @@ pub struct Combination(
 );
 
 impl Combination {
-    /// Returns a formatted version of the combination according to the given `LittleAlchemy2Database`.
-    pub fn display(&self, data: &LittleAlchemy2Database) -> String {
+    /// Returns a formatted version of the combination according to the given `GameStatus`.
+    pub fn display(&self, data: &GameStatus) -> String {
         format!("{} + {}", &data.elements[self.0].name, data.elements[self.1].name)
     }
 
@@ -110,7 +110,7 @@ impl std::fmt::Display for AlchemyElementError {
 }
 impl std::error::Error for AlchemyElementError {}
 impl AlchemyElement {
-    pub fn from_str<'a>(s: &str, data: &'a LittleAlchemy2Database) -> Result<&'a Self, AlchemyElementError> {
+    pub fn from_str<'a>(s: &str, data: &'a GameStatus) -> Result<&'a Self, AlchemyElementError> {
         if s.is_empty() {
             return Err(AlchemyElementError::EmptyString);
         }
@@ -134,7 +134,7 @@ impl AlchemyElement {
 
     pub fn display(
         &self,
-        data: &database::LittleAlchemy2Database,
+        data: &GameStatus,
         history: &history::History,
         subcommand: &Command,
     ) {
@@ -197,55 +197,23 @@ pub fn format_elements_list(elements: &[&AlchemyElement]) -> String {
     elements.iter().map(| x | x.name.to_string()).collect::<Vec<String>>().join(", ")
 }
 
-/// Displays a list of `Combination`s.
+/// Displays a list of `Combination`s, in the given `OutputFormat`.
 pub fn display_combinations_list(
     combinations: &[Combination],
-    data: &LittleAlchemy2Database,
+    data: &GameStatus,
     target_element: Option<&AlchemyElement>,
-    javascript: bool,
+    format: output::OutputFormat,
 ) {
-    if javascript {
-        if combinations.is_empty() {
-            return;
-        }
-        println!(r###"localStorage.setItem("stats", '{{"firstLaunch":0,"sessionsCount":1}}');"###);
-        println!(r###"localStorage.setItem("tutorials", '{{"shownText":["final","exhausted"]}}');"###);
-        println!(r###"var game_history = JSON.parse(localStorage.getItem("history")) || [];"###);
-        for combination in combinations {
-            println!(r###"game_history.push([{}, {}, 0]);"###, combination.0, combination.1);
-        }
-        println!(r###"localStorage.setItem("history", JSON.stringify(game_history));"###);
-        return;
-    }
-    let len = combinations.len();
-    for (i, combination) in combinations.iter().enumerate() {
-        let mut next_element_str = String::new();
-        // If it's not the last element, check in all the following combinations
-        // if there is the result (because there can be multiple results)
-        if i < len - 1 && target_element.is_some() {
-            let new_elements = data.elements.get_from_combination(combination);
-            'outer: for el in new_elements {
-                for combination_to_try in combinations {
-                    if combination_to_try.has(el.id) {
-                        next_element_str = format!(" (which gives the {})", el.name);
-                        break 'outer;
-                    }
-                }
-            }
-            assert!(!next_element_str.is_empty());
-        } else {
-            next_element_str = format!(
-                " (which gives the {})",
-                format_elements_list(&data.elements.get_from_combination(combination)[..]),
-            );
-        }
-
-        println!("- {}{next_element_str}", combination.display(data));
-    }
+    format.write_combinations(combinations, data, target_element);
 }
 
+pub mod binary;
 pub mod condition;
-pub mod database;
+pub mod diagnostics;
+pub mod game_status;
 pub mod history;
+pub mod output;
 pub mod path;
+pub mod query;
+pub mod search;
 pub mod serializers;
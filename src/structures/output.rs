@@ -0,0 +1,163 @@
+//! Pluggable rendering of elements and combination lists, selected by a CLI flag instead of
+//! the single hardcoded `javascript: bool` the program used to support.
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::Command;
+
+use super::{format_elements_list, game_status::GameStatus, history::History, AlchemyElement, Combination};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+/// How to render elements and combination lists.
+pub enum OutputFormat {
+    /// Human-readable text.
+    Text,
+    /// A `localStorage` JavaScript snippet that replays the combinations in-browser.
+    JavaScript,
+    /// A JSON array of `{a, b, gives}` objects.
+    Json,
+    /// A flat `a_name,b_name,result_name` CSV table.
+    Csv,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Text => "text",
+            Self::JavaScript => "javascript",
+            Self::Json => "json",
+            Self::Csv => "csv",
+        })
+    }
+}
+
+impl OutputFormat {
+    fn writer(self) -> &'static dyn OutputWriter {
+        match self {
+            Self::Text => &TextWriter,
+            Self::JavaScript => &JavaScriptWriter,
+            Self::Json => &JsonWriter,
+            Self::Csv => &CsvWriter,
+        }
+    }
+
+    /// Renders a list of combinations, in this format.
+    pub fn write_combinations(self, combinations: &[Combination], data: &GameStatus, target_element: Option<&AlchemyElement>) {
+        self.writer().write_combinations(combinations, data, target_element);
+    }
+
+    /// Renders a single element, in this format.
+    pub fn write_element(self, element: &AlchemyElement, data: &GameStatus, history: &History, subcommand: &Command) {
+        self.writer().write_element(element, data, history, subcommand);
+    }
+}
+
+trait OutputWriter {
+    fn write_combinations(&self, combinations: &[Combination], data: &GameStatus, target_element: Option<&AlchemyElement>);
+    fn write_element(&self, element: &AlchemyElement, data: &GameStatus, history: &History, subcommand: &Command);
+}
+
+struct TextWriter;
+impl OutputWriter for TextWriter {
+    fn write_combinations(&self, combinations: &[Combination], data: &GameStatus, target_element: Option<&AlchemyElement>) {
+        let len = combinations.len();
+        for (i, combination) in combinations.iter().enumerate() {
+            let next_element_str;
+            // If it's not the last element, check in all the following combinations
+            // if there is the result (because there can be multiple results)
+            if i < len - 1 && target_element.is_some() {
+                let new_elements = data.elements.get_from_combination(combination);
+                let mut found = String::new();
+                'outer: for el in new_elements {
+                    for combination_to_try in combinations {
+                        if combination_to_try.has(el.id) {
+                            found = format!(" (which gives the {})", el.name);
+                            break 'outer;
+                        }
+                    }
+                }
+                assert!(!found.is_empty());
+                next_element_str = found;
+            } else {
+                next_element_str = format!(
+                    " (which gives the {})",
+                    format_elements_list(&data.elements.get_from_combination(combination)[..]),
+                );
+            }
+
+            println!("- {}{next_element_str}", combination.display(data));
+        }
+    }
+
+    fn write_element(&self, element: &AlchemyElement, data: &GameStatus, history: &History, subcommand: &Command) {
+        element.display(data, history, subcommand);
+    }
+}
+
+struct JavaScriptWriter;
+impl OutputWriter for JavaScriptWriter {
+    fn write_combinations(&self, combinations: &[Combination], _data: &GameStatus, _target_element: Option<&AlchemyElement>) {
+        if combinations.is_empty() {
+            return;
+        }
+        println!(r###"localStorage.setItem("stats", '{{"firstLaunch":0,"sessionsCount":1}}');"###);
+        println!(r###"localStorage.setItem("tutorials", '{{"shownText":["final","exhausted"]}}');"###);
+        println!(r###"var game_history = JSON.parse(localStorage.getItem("history")) || [];"###);
+        for combination in combinations {
+            println!(r###"game_history.push([{}, {}, 0]);"###, combination.0, combination.1);
+        }
+        println!(r###"localStorage.setItem("history", JSON.stringify(game_history));"###);
+    }
+
+    fn write_element(&self, element: &AlchemyElement, data: &GameStatus, history: &History, subcommand: &Command) {
+        // There is no localStorage representation for a single element: fall back to text.
+        TextWriter.write_element(element, data, history, subcommand);
+    }
+}
+
+#[derive(Serialize)]
+struct JsonCombination {
+    a: String,
+    b: String,
+    gives: Vec<String>,
+}
+
+struct JsonWriter;
+impl OutputWriter for JsonWriter {
+    fn write_combinations(&self, combinations: &[Combination], data: &GameStatus, _target_element: Option<&AlchemyElement>) {
+        let entries: Vec<JsonCombination> = combinations.iter().map(| combination | JsonCombination {
+            a: data.elements[combination.0].name.clone(),
+            b: data.elements[combination.1].name.clone(),
+            gives: data.elements.get_from_combination(combination).iter().map(| el | el.name.clone()).collect(),
+        }).collect();
+        match serde_json::to_string_pretty(&entries) {
+            Ok(json) => println!("{json}"),
+            Err(err) => eprintln!("error serializing combinations: {err}"),
+        }
+    }
+
+    fn write_element(&self, element: &AlchemyElement, _data: &GameStatus, _history: &History, _subcommand: &Command) {
+        match serde_json::to_string_pretty(element) {
+            Ok(json) => println!("{json}"),
+            Err(err) => eprintln!("error serializing element: {err}"),
+        }
+    }
+}
+
+struct CsvWriter;
+impl OutputWriter for CsvWriter {
+    fn write_combinations(&self, combinations: &[Combination], data: &GameStatus, _target_element: Option<&AlchemyElement>) {
+        println!("a_name,b_name,result_name");
+        for combination in combinations {
+            let a = &data.elements[combination.0].name;
+            let b = &data.elements[combination.1].name;
+            for result in data.elements.get_from_combination(combination) {
+                println!("{a},{b},{}", result.name);
+            }
+        }
+    }
+
+    fn write_element(&self, element: &AlchemyElement, _data: &GameStatus, _history: &History, _subcommand: &Command) {
+        println!("{},{}", element.id, element.name);
+    }
+}
@@ -1,34 +1,9 @@
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{HashMap, HashSet};
 
-use super::{condition::Condition, database::LittleAlchemy2Database, AlchemyElement, Combination};
+use super::{condition::Condition, game_status::GameStatus, AlchemyElement, Combination};
 
 #[derive(Clone, Debug)]
-/// A wrapper for lists of `PathToCombination` objects.
-pub struct PathToCombinationList<'a>(
-    /// The `PathToCombination` list.
-    Vec<PathToCombination<'a>>,
-    /// The minimum number of combinations to get.
-    usize,
-);
-
-#[derive(Clone, Debug)]
-/// A container for two `PathToElement` items that represents the path to a combination.
-pub struct PathToCombination<'a>(
-    pub PathToElement<'a>,
-    pub PathToElement<'a>,
-);
-
-impl<'a> PathToCombination<'a> {
-    pub fn from(value: &Combination, data: &'a LittleAlchemy2Database) -> Self {
-        Self(
-            PathToElement::new(&data.elements[value.0]),
-            PathToElement::new(&data.elements[value.1]),
-        )
-    }
-}
-
-#[derive(Clone, Debug)]
-/// A container for a list of `PathWrapper`s that represents the path to an element.
+/// A container for a single element, used to compute the shortest crafting path to it.
 pub struct PathToElement<'a> {
     pub element: &'a AlchemyElement,
 }
@@ -38,115 +13,146 @@ impl<'a> PathToElement<'a> {
         Self { element }
     }
 
-    fn get_path_to_combinations<'b>(&self, data: &'b LittleAlchemy2Database) -> PathToCombinationList<'b> {
-        if data.acquired_elements.contains(&self.element.id) || self.element.prime {
-            PathToCombinationList(vec![], 0)
-        } else {
-            match &self.element.condition {
-                Condition::None => PathToCombinationList(
-                    self.element.combinations
-                    .iter()
-                    .map(| x | PathToCombination::from(x, data))
-                    .collect(),
-                    1,
-                ),
-                Condition::Progress(total) => {
-                    PathToCombinationList(
-                        data.elements
-                        .iter()
-                        .flat_map(| x | &x.combinations)
-                        .map(| x | PathToCombination::from(x, data))
-                        .collect(),
-                        *total - data.acquired_elements.len(),
-                    )
-                    // TODO
-                },
-                Condition::Elements(elements, min) => {
-                    let mut combinations = self.element.combinations.clone();
-                    let mut already_acquired = 0;
-                    for element_id in elements {
-                        if data.acquired_elements.contains(element_id) {
-                            already_acquired += 1;
-                            continue;
-                        }
-                        combinations.append(&mut data.elements[*element_id].combinations.clone());
-                    }
-                    assert!(*min - already_acquired > 0);
-                    PathToCombinationList(combinations.iter().map(| x | PathToCombination::from(x, data)).collect(), *min - already_acquired)
-                },
-            }
-        }
+    /// Returns an ordered list of combinations that produces `self.element`, with the lowest
+    /// cost as computed by [`shortest_path`].
+    ///
+    /// See [`shortest_path`] for how conditioned elements are costed.
+    pub fn shortest_path(&self, data: &GameStatus) -> Vec<Combination> {
+        shortest_path(data, self.element.id)
     }
+}
 
-    pub fn advance_one_level<'b>(
-        &self,
-        data: &'b LittleAlchemy2Database,
-        element_to_combinations: &mut HashMap<u16, PathToCombinationList<'b>>,
-        current_path: &[u16],
-        recursive_history: &mut HashMap<u16, bool>,
-        recursive: bool,
-    ) -> Result<(), Vec<Combination>> {
-        if current_path.contains(&self.element.id) {
-            return Ok(());
-        }
-        // If there are no combinations filled in, add them and don't recurse
-        if let Entry::Vacant(entry) = element_to_combinations.entry(self.element.id) {
-            entry.insert(self.get_path_to_combinations(data));
-            assert!(!recursive);
-        } else {
-            // Don't do assertions here (if an element was already filled in before, don't recurse)
+/// Computes the minimum-combination crafting plan for `target`, treating the recipe graph as
+/// an AND/OR hypergraph: a `Combination(a, b) -> result` edge can fire once both `a` and `b`
+/// have a known cost, relaxing `result`'s cost to `cost(a) + cost(b) + 1`. This is a worklist
+/// relaxation (values only ever decrease, so iterating to a fixpoint is safe) rather than a
+/// plain Dijkstra, since an edge needs *two* settled predecessors instead of one.
+///
+/// Elements gated by a [`Condition`] never need a combination of their own to unlock:
+/// - [`Condition::Elements`] becomes available once its cheapest `min` listed elements are
+///   acquired, so its cost is the sum of their costs, with no combination contributed.
+/// - [`Condition::Progress`] gates on how many *other* elements are already resolved rather
+///   than on specific combinations, so once satisfied it costs nothing extra on top of what's
+///   already in the plan.
+/// - [`Condition::All`] costs the sum of its sub-conditions' costs, and [`Condition::Any`] the
+///   cost of its cheapest satisfiable sub-condition.
+fn shortest_path(data: &GameStatus, target: u16) -> Vec<Combination> {
+    let mut cost: HashMap<u16, usize> = HashMap::new();
+    let mut predecessor: HashMap<u16, Combination> = HashMap::new();
+    let mut condition_deps: HashMap<u16, Vec<u16>> = HashMap::new();
 
-            // puddle
-            // = water (doesn't exist in hashmap = not recursive) + pond (not filled = not recursive)
-            // = pond (alrady filled but not recursive!) + pond (same thing)
-            // = ...
-            recursive_history.insert(self.element.id, false);
+    for item in data.elements.iter() {
+        if item.prime || data.acquired_elements.contains(&item.id) {
+            cost.insert(item.id, 0);
         }
-        let combinations = element_to_combinations[&self.element.id].clone();
+    }
 
-        let min = combinations.1;
-        let combs = combinations.0;
-        // If there are no combinations, stop here and propagate the "error"
-        if combs.is_empty() {
-            assert_eq!(min, 0);
-            return Err(vec![]);
-        }
-        // If we just filled the combinations, don't recurse and stop here
-        if !recursive {
-            return Ok(());
-        }
-        let mut counter = 0;
-        let mut ret_chains = vec![];
-        // Advance everything from one level
-        for comb in combs {
-            let id0 = comb.0.element.id;
-            let id1 = comb.1.element.id;
-            let mut final_chain = vec![];
-            let mut matched: u8 = 0;
-            let comb_0 = &comb.0;
-            let comb_1 = &comb.1;
-            for path_to_el in &mut [comb_0, comb_1] {
-                let mut new_path = current_path.to_owned();
-                new_path.push(self.element.id);
-                let recursive = *recursive_history.entry(path_to_el.element.id)
-                    .or_insert_with(|| element_to_combinations.contains_key(&path_to_el.element.id));
-                match path_to_el.advance_one_level(data, element_to_combinations, &new_path[..], recursive_history, recursive) {
-                    Ok(()) => {},
-                    Err(mut chain) => {
-                        final_chain.append(&mut chain);
-                        matched += 1;
-                    },
+    loop {
+        let mut changed = false;
+
+        for item in data.elements.iter() {
+            for comb in &item.combinations {
+                let (Some(&cost_a), Some(&cost_b)) = (cost.get(&comb.0), cost.get(&comb.1)) else { continue; };
+                let candidate = cost_a + cost_b + 1;
+                if cost.get(&item.id).is_none_or(| &current | candidate < current) {
+                    cost.insert(item.id, candidate);
+                    predecessor.insert(item.id, comb.clone());
+                    changed = true;
                 }
             }
-            if matched == 2 {
-                final_chain.push(Combination(id0, id1));
-                ret_chains.push(final_chain);
-                counter += 1;
-                if counter >= min {
-                    return Err(ret_chains.concat());
+
+            if let Some(candidate) = condition_cost(&item.condition, &cost) {
+                if cost.get(&item.id).is_none_or(| &current | candidate < current) {
+                    cost.insert(item.id, candidate);
+                    predecessor.remove(&item.id);
+                    condition_deps.insert(item.id, condition_ready_dependencies(&item.condition, &cost));
+                    changed = true;
                 }
             }
         }
-        Ok(())
+
+        if !changed {
+            break;
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut ordered = vec![];
+    collect_path(target, &predecessor, &condition_deps, &mut visited, &mut ordered);
+    ordered
+}
+
+/// Returns the cost this condition would give its element if resolved right now, or `None` if
+/// it can't be resolved yet given the current `cost` map.
+fn condition_cost(condition: &Condition, cost: &HashMap<u16, usize>) -> Option<usize> {
+    match condition {
+        Condition::None => None,
+        Condition::Progress(total) => (cost.len() > *total).then_some(0),
+        Condition::Elements(ids, min) => {
+            let mut costs: Vec<usize> = ids.iter().filter_map(| id | cost.get(id).copied()).collect();
+            (costs.len() >= *min).then(|| {
+                costs.sort_unstable();
+                costs[..*min].iter().sum()
+            })
+        },
+        Condition::All(conditions) => {
+            let mut total = 0;
+            for sub in conditions {
+                total += condition_cost(sub, cost)?;
+            }
+            Some(total)
+        },
+        Condition::Any(conditions) => conditions.iter().filter_map(| sub | condition_cost(sub, cost)).min(),
+    }
+}
+
+/// Returns the other elements whose own crafting plan must be included for this condition to
+/// hold (only `Condition::Elements` names specific elements; the other variants are satisfied
+/// by the state of the plan as a whole, so they add no extra dependency).
+fn condition_ready_dependencies(condition: &Condition, cost: &HashMap<u16, usize>) -> Vec<u16> {
+    match condition {
+        Condition::Elements(ids, min) => {
+            let mut candidates: Vec<(u16, usize)> = ids.iter()
+                .filter_map(| &id | cost.get(&id).map(| &c | (id, c)))
+                .collect();
+            candidates.sort_by_key(| &(_, c) | c);
+            candidates.truncate(*min);
+            candidates.into_iter().map(| (id, _) | id).collect()
+        },
+        Condition::All(conditions) => {
+            conditions.iter().flat_map(| sub | condition_ready_dependencies(sub, cost)).collect()
+        },
+        // Only the cheapest satisfiable branch is needed, matching `condition_cost`.
+        Condition::Any(conditions) => conditions.iter()
+            .filter_map(| sub | condition_cost(sub, cost).map(| c | (c, sub)))
+            .min_by_key(| &(c, _) | c)
+            .map(| (_, sub) | condition_ready_dependencies(sub, cost))
+            .unwrap_or_default(),
+        Condition::None | Condition::Progress(_) => vec![],
+    }
+}
+
+/// Walks the `predecessor`/`condition_deps` chains from `id` down to already-acquired
+/// elements, appending each combination once both its ingredients have been appended.
+fn collect_path(
+    id: u16,
+    predecessor: &HashMap<u16, Combination>,
+    condition_deps: &HashMap<u16, Vec<u16>>,
+    visited: &mut HashSet<u16>,
+    ordered: &mut Vec<Combination>,
+) {
+    if !visited.insert(id) {
+        return;
+    }
+    if let Some(comb) = predecessor.get(&id) {
+        collect_path(comb.0, predecessor, condition_deps, visited, ordered);
+        collect_path(comb.1, predecessor, condition_deps, visited, ordered);
+        if !ordered.contains(comb) {
+            ordered.push(comb.clone());
+        }
+    } else if let Some(deps) = condition_deps.get(&id) {
+        for &dep in deps {
+            collect_path(dep, predecessor, condition_deps, visited, ordered);
+        }
     }
 }
@@ -0,0 +1,168 @@
+//! A small path/query DSL for selecting sets of `AlchemyElement`s by composing navigation
+//! steps and predicates, e.g. `[prime]/products/[final]`.
+use std::collections::HashSet;
+
+use super::{game_status::GameStatus, AlchemyElement};
+
+#[derive(Debug)]
+/// An error while parsing a query string.
+pub enum QueryError {
+    /// A `/`-separated step wasn't a known navigation keyword or a `[predicate]`.
+    UnknownStep(String),
+    /// A bracketed predicate wasn't one of the known kinds.
+    UnknownPredicate(String),
+    /// A predicate looked like `name ~ ...` but wasn't valid.
+    MalformedPredicate(String),
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownStep(s) => f.write_fmt(format_args!("unknown query step: {s}")),
+            Self::UnknownPredicate(s) => f.write_fmt(format_args!("unknown predicate: [{s}]")),
+            Self::MalformedPredicate(s) => f.write_fmt(format_args!("malformed predicate: [{s}]")),
+        }
+    }
+}
+impl std::error::Error for QueryError {}
+
+#[derive(Clone, Debug)]
+enum Predicate {
+    Prime,
+    Final,
+    Base,
+    Hidden,
+    Condition,
+    NameContains(String),
+}
+
+impl Predicate {
+    fn matches(&self, element: &AlchemyElement) -> bool {
+        match self {
+            Self::Prime => element.prime,
+            Self::Final => element.final_,
+            Self::Base => element.base,
+            Self::Hidden => element.hidden,
+            Self::Condition => !element.condition.is_none(),
+            Self::NameContains(needle) => element.name.to_lowercase().contains(&needle.to_lowercase()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Step {
+    /// Expands every current element into the two ingredients of each of its combinations.
+    Ingredients,
+    /// Expands every current element into everything it directly creates.
+    Creates,
+    /// Expands every current element into everything reachable by combining it, transitively.
+    Products,
+    /// Keeps only the current elements matching the predicate.
+    Predicate(Predicate),
+}
+
+#[derive(Clone, Debug)]
+/// A parsed query: a sequence of navigation/predicate steps evaluated left to right.
+pub struct Query(Vec<Step>);
+
+fn parse_predicate(s: &str) -> Result<Predicate, QueryError> {
+    match s {
+        "prime" => Ok(Predicate::Prime),
+        "final" => Ok(Predicate::Final),
+        "base" => Ok(Predicate::Base),
+        "hidden" => Ok(Predicate::Hidden),
+        "condition" => Ok(Predicate::Condition),
+        _ => {
+            let (field, rest) = s.split_once('~').ok_or_else(|| QueryError::UnknownPredicate(s.to_string()))?;
+            if field.trim() != "name" {
+                return Err(QueryError::UnknownPredicate(s.to_string()));
+            }
+            let value = rest.trim()
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .ok_or_else(|| QueryError::MalformedPredicate(s.to_string()))?;
+            Ok(Predicate::NameContains(value.to_string()))
+        },
+    }
+}
+
+/// Parses a query string like `[prime]/products/[final]` into a `Query`.
+pub fn parse_query(s: &str) -> Result<Query, QueryError> {
+    let mut steps = vec![];
+    for segment in s.split('/') {
+        let segment = segment.trim();
+        if segment.is_empty() {
+            continue;
+        }
+        if let Some(inner) = segment.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            steps.push(Step::Predicate(parse_predicate(inner.trim())?));
+        } else {
+            steps.push(match segment {
+                "ingredients" => Step::Ingredients,
+                "creates" => Step::Creates,
+                "products" => Step::Products,
+                other => return Err(QueryError::UnknownStep(other.to_string())),
+            });
+        }
+    }
+    Ok(Query(steps))
+}
+
+fn step_ingredients(ids: &HashSet<u16>, data: &GameStatus) -> HashSet<u16> {
+    let mut result = HashSet::new();
+    for &id in ids {
+        if let Some(element) = data.elements.get(id) {
+            for comb in &element.combinations {
+                result.insert(comb.0);
+                result.insert(comb.1);
+            }
+        }
+    }
+    result
+}
+
+fn step_creates(ids: &HashSet<u16>, data: &GameStatus) -> HashSet<u16> {
+    let mut result = HashSet::new();
+    for &id in ids {
+        if let Some(element) = data.elements.get(id) {
+            result.extend(element.can_create.iter().copied());
+        }
+    }
+    result
+}
+
+fn step_products(ids: &HashSet<u16>, data: &GameStatus) -> HashSet<u16> {
+    let mut result = HashSet::new();
+    let mut frontier: Vec<u16> = ids.iter().copied().collect();
+    while let Some(id) = frontier.pop() {
+        let Some(element) = data.elements.get(id) else { continue; };
+        for &created in &element.can_create {
+            if result.insert(created) {
+                frontier.push(created);
+            }
+        }
+    }
+    result
+}
+
+impl Query {
+    /// Evaluates the query against `data`, returning the matching elements ordered by id.
+    pub fn eval<'a>(&self, data: &'a GameStatus) -> Vec<&'a AlchemyElement> {
+        let mut current: HashSet<u16> = data.elements.iter().map(|element| element.id).collect();
+
+        for step in &self.0 {
+            current = match step {
+                Step::Ingredients => step_ingredients(&current, data),
+                Step::Creates => step_creates(&current, data),
+                Step::Products => step_products(&current, data),
+                Step::Predicate(predicate) => current.into_iter()
+                    .filter(|id| data.elements.get(*id).is_some_and(|element| predicate.matches(element)))
+                    .collect(),
+            };
+        }
+
+        let mut elements: Vec<&AlchemyElement> = current.into_iter().filter_map(|id| data.elements.get(id)).collect();
+        elements.sort_by_key(|element| element.id);
+        elements
+    }
+}
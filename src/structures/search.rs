@@ -0,0 +1,127 @@
+use super::{game_status::ElementsList, AlchemyElement};
+
+/// The MeiliSearch-style typo budget for a word of the given length.
+fn typo_budget(len: usize) -> usize {
+    if len <= 4 {
+        0
+    } else if len <= 8 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Computes the Levenshtein distance between `a` and `b`, aborting early (returning `None`)
+/// as soon as every cell of the current row exceeds `budget`.
+fn bounded_levenshtein(a: &str, b: &str, budget: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > budget {
+        return None;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut cur = vec![0; b.len() + 1];
+        cur[0] = i + 1;
+        let mut row_min = cur[0];
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            cur[j + 1] = (prev[j + 1] + 1).min(cur[j] + 1).min(prev[j] + cost);
+            row_min = row_min.min(cur[j + 1]);
+        }
+        if row_min > budget {
+            return None;
+        }
+        prev = cur;
+    }
+
+    let distance = prev[b.len()];
+    (distance <= budget).then_some(distance)
+}
+
+/// Returns the edit distance if `query` matches `word` within the typo budget for `query`'s
+/// length, or `None` otherwise.
+fn word_matches(query: &str, word: &str) -> Option<usize> {
+    bounded_levenshtein(query, word, typo_budget(query.chars().count()))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct SearchScore {
+    /// 0 = exact match, 1 = prefix, 2 = substring, 3 = typo-tolerant.
+    tier: u8,
+    /// Edit distance accumulated across words (0 for tiers 0-2).
+    distance: usize,
+    /// Number of name words not accounted for by the query.
+    extra_words: usize,
+    /// Ascending element id, used as the final tiebreaker.
+    id: u16,
+}
+
+/// Scores `name` against `query`, or returns `None` if it doesn't match at all.
+fn score(query: &str, query_words: &[&str], name: &str, name_words: &[&str], id: u16) -> Option<SearchScore> {
+    if query == name {
+        return Some(SearchScore { tier: 0, distance: 0, extra_words: name_words.len().saturating_sub(query_words.len()), id });
+    }
+    if name.starts_with(query) {
+        return Some(SearchScore { tier: 1, distance: 0, extra_words: name_words.len().saturating_sub(query_words.len()), id });
+    }
+    if name.contains(query) {
+        return Some(SearchScore { tier: 2, distance: 0, extra_words: name_words.len().saturating_sub(query_words.len()), id });
+    }
+
+    // Each query word must match some name word within its typo budget.
+    let mut total_distance = 0;
+    let mut used = vec![false; name_words.len()];
+    for query_word in query_words {
+        let mut best: Option<(usize, usize)> = None;
+        for (i, name_word) in name_words.iter().enumerate() {
+            if used[i] {
+                continue;
+            }
+            if let Some(distance) = word_matches(query_word, name_word) {
+                if best.is_none_or(|(_, best_distance)| distance < best_distance) {
+                    best = Some((i, distance));
+                }
+            }
+        }
+        let (i, distance) = best?;
+        used[i] = true;
+        total_distance += distance;
+    }
+
+    Some(SearchScore {
+        tier: 3,
+        distance: total_distance,
+        extra_words: name_words.len() - query_words.len().min(name_words.len()),
+        id,
+    })
+}
+
+impl ElementsList {
+    /// Searches the elements for names matching `query`, tolerating typos and partial input.
+    ///
+    /// Results are ranked by tier (exact, prefix, substring, then typo-tolerant), then by edit
+    /// distance, then by the number of extra words in the name, then by ascending element id,
+    /// and truncated to `max_results`.
+    pub fn search(&self, query: &str, max_results: usize) -> Vec<&AlchemyElement> {
+        let query = query.to_lowercase();
+        let query_words: Vec<&str> = query.split_whitespace().collect();
+        if query_words.is_empty() {
+            return vec![];
+        }
+
+        let mut matches: Vec<(SearchScore, &AlchemyElement)> = self
+            .iter()
+            .filter_map(|element| {
+                let name = element.name.to_lowercase();
+                let name_words: Vec<&str> = name.split_whitespace().collect();
+                score(&query, &query_words, &name, &name_words, element.id).map(|s| (s, element))
+            })
+            .collect();
+
+        matches.sort_by_key(|(s, _)| *s);
+        matches.truncate(max_results);
+        matches.into_iter().map(|(_, element)| element).collect()
+    }
+}
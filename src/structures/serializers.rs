@@ -1,5 +1,5 @@
 pub mod number_as_str {
-    use serde::{self, Deserialize, Deserializer, Serializer};
+    use serde::{self, de::{self, Visitor}, Deserializer, Serializer};
 
     #[allow(clippy::trivially_copy_pass_by_ref)]
     pub fn serialize<S>(number: &u16, serializer: S) -> Result<S::Ok, S::Error>
@@ -7,15 +7,30 @@ pub mod number_as_str {
         serializer.serialize_str(&number.to_string())
     }
 
+    struct NumberAsStrVisitor;
+    impl<'de> Visitor<'de> for NumberAsStrVisitor {
+        type Value = u16;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a string containing a u16")
+        }
+
+        // Parses straight out of the borrowed/buffered `&str` the deserializer hands us,
+        // instead of going through `String::deserialize` and throwing the allocation away.
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where E: de::Error {
+            value.parse().map_err(de::Error::custom)
+        }
+    }
+
     pub fn deserialize<'de, D>(deserializer: D) -> Result<u16, D::Error>
     where D: Deserializer<'de> {
-        let s: String = String::deserialize(deserializer)?;
-        s.parse::<u16>().map_err(serde::de::Error::custom)
+        deserializer.deserialize_str(NumberAsStrVisitor)
     }
 }
 
 pub mod number_list_as_str_list {
-    use serde::{self, Deserialize, Deserializer, Serializer, ser::SerializeSeq};
+    use serde::{self, de::{self, SeqAccess, Visitor}, Deserialize, Deserializer, Serializer, ser::SerializeSeq};
 
     #[allow(clippy::trivially_copy_pass_by_ref)]
     pub fn serialize<S>(list: &Vec<u16>, serializer: S) -> Result<S::Ok, S::Error>
@@ -27,13 +42,50 @@ pub mod number_list_as_str_list {
         seq.end()
     }
 
+    struct StrNumberVisitor;
+    impl<'de> Visitor<'de> for StrNumberVisitor {
+        type Value = u16;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a string containing a u16")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where E: de::Error {
+            value.parse().map_err(de::Error::custom)
+        }
+    }
+
+    /// A single list entry, deserialized straight from its `&str` without an intermediate
+    /// `Vec<String>` (as plain `Vec::<String>::deserialize` would need).
+    struct StrNumber(u16);
+    impl<'de> Deserialize<'de> for StrNumber {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de> {
+            deserializer.deserialize_str(StrNumberVisitor).map(StrNumber)
+        }
+    }
+
+    struct NumberListVisitor;
+    impl<'de> Visitor<'de> for NumberListVisitor {
+        type Value = Vec<u16>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a list of strings containing u16s")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where A: SeqAccess<'de> {
+            let mut ret = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(StrNumber(n)) = seq.next_element()? {
+                ret.push(n);
+            }
+            Ok(ret)
+        }
+    }
+
     pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u16>, D::Error>
     where D: Deserializer<'de> {
-        let list: Vec<String> = Vec::deserialize(deserializer)?;
-        let mut ret: Vec<u16> = vec![];
-        for item in list {
-            ret.push(item.parse().map_err(serde::de::Error::custom)?);
-        }
-        Ok(ret)
+        deserializer.deserialize_seq(NumberListVisitor)
     }
 }